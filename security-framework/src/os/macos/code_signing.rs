@@ -1,31 +1,59 @@
 //! Code signing services.
 
-use std::{mem::MaybeUninit, str::FromStr};
+use std::{
+    collections::HashMap,
+    ffi::c_void,
+    mem::MaybeUninit,
+    str::FromStr,
+    sync::{Mutex, OnceLock},
+};
 
 use core_foundation::{
-    base::{TCFType, TCFTypeRef, ToVoid},
-    data::CFDataRef,
-    dictionary::CFMutableDictionary,
-    number::CFNumber,
+    array::{CFArray, CFArrayRef},
+    base::{CFNull, TCFType, TCFTypeRef, ToVoid},
+    data::{CFData, CFDataRef, CFMutableData},
+    date::{CFDate, CFDateRef},
+    dictionary::{CFDictionary, CFDictionaryRef, CFMutableDictionary},
+    error::CFError,
+    number::{CFNumber, CFNumberRef},
     string::{CFString, CFStringRef},
-    url::CFURL,
+    url::{CFURL, CFURLRef},
 };
-use libc::pid_t;
-use security_framework_sys::code_signing::{
-    kSecCSBasicValidateOnly, kSecCSCheckAllArchitectures, kSecCSCheckGatekeeperArchitectures,
-    kSecCSCheckNestedCode, kSecCSCheckTrustedAnchors, kSecCSConsiderExpiration,
-    kSecCSDoNotValidateExecutable, kSecCSDoNotValidateResources, kSecCSEnforceRevocationChecks,
-    kSecCSFullReport, kSecCSNoNetworkAccess, kSecCSQuickCheck, kSecCSReportProgress,
-    kSecCSRestrictSidebandData, kSecCSRestrictSymlinks, kSecCSRestrictToAppLike,
-    kSecCSSingleThreaded, kSecCSStrictValidate, kSecCSUseSoftwareSigningCert, kSecCSValidatePEH,
-    kSecGuestAttributeAudit, kSecGuestAttributePid, SecCodeCheckValidity,
-    SecCodeCopyGuestWithAttributes, SecCodeCopyPath, SecCodeCopySelf, SecCodeGetTypeID, SecCodeRef,
-    SecRequirementCreateWithString, SecRequirementGetTypeID, SecRequirementRef,
-    SecStaticCodeCheckValidity, SecStaticCodeCreateWithPath, SecStaticCodeGetTypeID,
-    SecStaticCodeRef,
+use core_foundation_sys::{base::CFTypeRef, boolean::kCFBooleanFalse};
+use libc::{mach_port_t, pid_t};
+use security_framework_sys::{
+    certificate::SecCertificateRef,
+    code_signing::{
+        kSecCSBasicValidateOnly, kSecCSCheckAllArchitectures, kSecCSCheckGatekeeperArchitectures,
+        kSecCSCheckNestedCode, kSecCSCheckTrustedAnchors, kSecCSConsiderExpiration,
+        kSecCSContentInformation, kSecCSDoNotValidateExecutable, kSecCSDoNotValidateResources,
+        kSecCSDynamicInformation, kSecCSEnforceRevocationChecks, kSecCSFullReport,
+        kSecCSInternalInformation, kSecCSNoNetworkAccess, kSecCSQuickCheck, kSecCSReportProgress,
+        kSecCSRequirementInformation, kSecCSRestrictSidebandData, kSecCSRestrictSymlinks,
+        kSecCSRestrictToAppLike, kSecCSSigningInformation, kSecCSSingleThreaded,
+        kSecCSStrictValidate, kSecCSUseSoftwareSigningCert, kSecCSValidatePEH,
+        kSecCodeInfoCdHashes, kSecCodeInfoCertificates, kSecCodeInfoEntitlementsDict,
+        kSecCodeInfoFlags, kSecCodeInfoIdentifier,
+        kSecCodeInfoImplicitDesignatedRequirement, kSecCodeInfoTeamIdentifier,
+        kSecCodeInfoTimestamp, kSecCodeInfoUnique, kSecCodeSignerDetached,
+        kSecCodeSignerEntitlements, kSecCodeSignerFlags, kSecCodeSignerIdentifier,
+        kSecCodeSignerIdentity, kSecCodeSignerRequirements, kSecCodeSignerSigningTime,
+        kSecGuestAttributeArchitecture, kSecGuestAttributeAudit, kSecGuestAttributeCanonical,
+        kSecGuestAttributeDynamicCode, kSecGuestAttributeDynamicCodeInfoPlist,
+        kSecGuestAttributeHash, kSecGuestAttributeMachPort, kSecGuestAttributePid,
+        kSecGuestAttributeSubarchitecture, SecCodeCheckValidity,
+        SecCodeCopyDesignatedRequirement, SecCodeCopyGuestWithAttributes, SecCodeCopyPath,
+        SecCodeCopySelf, SecCodeCopySigningInformation, SecCodeGetTypeID, SecCodeRef,
+        SecCodeSetDetachedSignature, SecCodeSignerAddSignature, SecCodeSignerCreate,
+        SecCodeSignerGetTypeID, SecCodeSignerRef, SecRequirementCopyData, SecRequirementCopyString,
+        SecRequirementCreateWithData, SecRequirementCreateWithString, SecRequirementGetTypeID,
+        SecRequirementRef, SecStaticCodeCheckValidity, SecStaticCodeCheckValidityWithErrors,
+        SecStaticCodeCreateWithPath, SecStaticCodeGetTypeID, SecStaticCodeRef,
+        SecStaticCodeSetCallback,
+    },
 };
 
-use crate::{cvt, Result};
+use crate::{certificate::SecCertificate, cvt, identity::SecIdentity, Result};
 
 bitflags::bitflags! {
 
@@ -108,6 +136,131 @@ impl Default for Flags {
     }
 }
 
+bitflags::bitflags! {
+    /// Selects which categories of information `signing_information` should
+    /// return. These are a separate set from the [`Flags`] passed to most
+    /// other code signing functions; `signing_information` takes only these.
+    pub struct InformationFlags: u32 {
+        /// Return no information beyond the fact that the call succeeded.
+        const NONE = 0;
+
+        /// Return internal information about the code object itself, such
+        /// as its source location and format.
+        const INTERNAL_INFORMATION = kSecCSInternalInformation;
+
+        /// Return information about the signature attached to the code, such
+        /// as its identifier, team identifier, and certificate chain.
+        const SIGNING_INFORMATION = kSecCSSigningInformation;
+
+        /// Return information about the code's internal requirements.
+        const REQUIREMENT_INFORMATION = kSecCSRequirementInformation;
+
+        /// Return dynamic information that only applies to running code.
+        const DYNAMIC_INFORMATION = kSecCSDynamicInformation;
+
+        /// Return information about the code's content, such as its main
+        /// executable and resources.
+        const CONTENT_INFORMATION = kSecCSContentInformation;
+    }
+}
+
+impl Default for InformationFlags {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// Signing information about a piece of code, as returned by
+/// [`SecCode::signing_information`] or [`SecStaticCode::signing_information`].
+///
+/// Which of these accessors return data depends on the [`InformationFlags`]
+/// that were passed when the information was requested.
+pub struct SigningInformation {
+    inner: CFDictionary,
+}
+
+impl SigningInformation {
+    fn find(&self, key: CFStringRef) -> Option<*const c_void> {
+        self.inner.find(key as *const c_void).map(|value| *value)
+    }
+
+    /// The code's unique identifier, typically a bundle ID.
+    pub fn identifier(&self) -> Option<String> {
+        self.find(unsafe { kSecCodeInfoIdentifier })
+            .map(|value| unsafe { CFString::wrap_under_get_rule(value as CFStringRef).to_string() })
+    }
+
+    /// The Apple-issued team identifier of the certificate that signed the
+    /// code, if any.
+    pub fn team_identifier(&self) -> Option<String> {
+        self.find(unsafe { kSecCodeInfoTeamIdentifier })
+            .map(|value| unsafe { CFString::wrap_under_get_rule(value as CFStringRef).to_string() })
+    }
+
+    /// The code directory hash ("cdhash") of the code's primary architecture.
+    pub fn cdhash(&self) -> Option<Vec<u8>> {
+        self.find(unsafe { kSecCodeInfoUnique })
+            .map(|value| unsafe { CFData::wrap_under_get_rule(value as CFDataRef).to_vec() })
+    }
+
+    /// The code directory hashes of every architecture contained in the
+    /// code, in the same order as the code's architectures.
+    pub fn cdhashes(&self) -> Option<Vec<Vec<u8>>> {
+        self.find(unsafe { kSecCodeInfoCdHashes }).map(|value| {
+            let hashes = unsafe { CFArray::<CFDataRef>::wrap_under_get_rule(value as CFArrayRef) };
+            hashes
+                .iter()
+                .map(|data| unsafe { CFData::wrap_under_get_rule(*data).to_vec() })
+                .collect()
+        })
+    }
+
+    /// The chain of certificates that signed the code, leaf certificate
+    /// first.
+    pub fn certificates(&self) -> Option<Vec<SecCertificate>> {
+        self.find(unsafe { kSecCodeInfoCertificates }).map(|value| {
+            let certificates =
+                unsafe { CFArray::<SecCertificateRef>::wrap_under_get_rule(value as CFArrayRef) };
+            certificates
+                .iter()
+                .map(|certificate| unsafe { SecCertificate::wrap_under_get_rule(*certificate) })
+                .collect()
+        })
+    }
+
+    /// The entitlements dictionary embedded in the code's signature, if any.
+    pub fn entitlements(&self) -> Option<CFDictionary> {
+        self.find(unsafe { kSecCodeInfoEntitlementsDict })
+            .map(|value| unsafe { CFDictionary::wrap_under_get_rule(value as CFDictionaryRef) })
+    }
+
+    /// The `SecCodeSignatureFlags` recorded in the code's signature.
+    pub fn flags(&self) -> Option<u32> {
+        self.find(unsafe { kSecCodeInfoFlags }).and_then(|value| {
+            unsafe { CFNumber::wrap_under_get_rule(value as CFNumberRef) }
+                .to_i64()
+                .map(|flags| flags as u32)
+        })
+    }
+
+    /// The time the signature was timestamped by a trusted timestamping
+    /// authority, if any.
+    pub fn timestamp(&self) -> Option<CFDate> {
+        self.find(unsafe { kSecCodeInfoTimestamp })
+            .map(|value| unsafe { CFDate::wrap_under_get_rule(value as CFDateRef) })
+    }
+
+    /// The designated requirement that would be implicitly assumed for this
+    /// code if none were specified explicitly.
+    pub fn implicit_designated_requirement(&self) -> Option<SecRequirement> {
+        self.find(unsafe { kSecCodeInfoImplicitDesignatedRequirement })
+            .map(|value| unsafe {
+                SecRequirement::wrap_under_get_rule(value as SecRequirementRef)
+            })
+    }
+}
+
 /// A helper to create guest attributes, which are normally passed as a
 /// `CFDictionary` with varying types.
 pub struct GuestAttributes {
@@ -115,15 +268,6 @@ pub struct GuestAttributes {
 }
 
 impl GuestAttributes {
-    // Not implemented:
-    // - architecture
-    // - canonical
-    // - dynamic code
-    // - dynamic code info plist
-    // - hash
-    // - mach port
-    // - sub-architecture
-
     /// The guest's audit token.
     pub fn set_audit_token(&mut self, token: CFDataRef) {
         let key = unsafe { CFString::wrap_under_get_rule(kSecGuestAttributeAudit) };
@@ -137,6 +281,64 @@ impl GuestAttributes {
         self.inner.add(&key.as_CFTypeRef(), &pid.as_CFTypeRef());
     }
 
+    /// The guest's CPU architecture, as a `cpu_type_t` from
+    /// `mach/machine.h`. Typically combined with
+    /// [`set_subarchitecture`](Self::set_subarchitecture).
+    pub fn set_architecture(&mut self, cpu_type: i32) {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecGuestAttributeArchitecture) };
+        let cpu_type = CFNumber::from(cpu_type);
+        self.inner.add(&key.as_CFTypeRef(), &cpu_type.as_CFTypeRef());
+    }
+
+    /// The guest's CPU sub-architecture, as a `cpu_subtype_t` from
+    /// `mach/machine.h`.
+    pub fn set_subarchitecture(&mut self, cpu_subtype: i32) {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecGuestAttributeSubarchitecture) };
+        let cpu_subtype = CFNumber::from(cpu_subtype);
+        self.inner
+            .add(&key.as_CFTypeRef(), &cpu_subtype.as_CFTypeRef());
+    }
+
+    /// A requirement the guest's canonical (host-assigned) code signature
+    /// must satisfy.
+    pub fn set_canonical(&mut self, requirement: &SecRequirement) {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecGuestAttributeCanonical) };
+        self.inner
+            .add(&key.as_CFTypeRef(), &requirement.as_CFTypeRef());
+    }
+
+    /// The expected code directory hash ("cdhash") of the guest's dynamic
+    /// code identity. Combined with the audit token, this identifies a
+    /// connecting XPC or Mach peer by pid *and* cryptographic identity,
+    /// rather than pid alone.
+    pub fn set_hash(&mut self, hash: CFDataRef) {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecGuestAttributeHash) };
+        self.inner.add(&key.as_CFTypeRef(), &hash.as_void_ptr());
+    }
+
+    /// The guest's Mach port.
+    pub fn set_mach_port(&mut self, port: mach_port_t) {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecGuestAttributeMachPort) };
+        let port = CFNumber::from(i64::from(port));
+        self.inner.add(&key.as_CFTypeRef(), &port.as_CFTypeRef());
+    }
+
+    /// The guest's own dynamic code object, identifying it directly rather
+    /// than through attributes of its host.
+    pub fn set_dynamic_code(&mut self, code: &SecCode) {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecGuestAttributeDynamicCode) };
+        self.inner.add(&key.as_CFTypeRef(), &code.as_CFTypeRef());
+    }
+
+    /// The guest's `Info.plist`, as it would be reported for a running piece
+    /// of dynamic code.
+    pub fn set_dynamic_code_info_plist(&mut self, info_plist: &CFDictionary) {
+        let key =
+            unsafe { CFString::wrap_under_get_rule(kSecGuestAttributeDynamicCodeInfoPlist) };
+        self.inner
+            .add(&key.as_CFTypeRef(), &info_plist.as_CFTypeRef());
+    }
+
     /// Support for arbirtary guest attributes.
     pub fn set_other<V: ToVoid<V>>(&mut self, key: CFStringRef, value: V) {
         self.inner.add(&key.as_void_ptr(), &value.to_void());
@@ -168,6 +370,59 @@ impl FromStr for SecRequirement {
     }
 }
 
+impl SecRequirement {
+    /// Re-renders the compiled requirement back into its requirement-language
+    /// source text, the inverse of [`FromStr::from_str`].
+    ///
+    /// Named `copy_string` rather than `to_string` so it doesn't collide
+    /// with (and risk being silently shadowed by) a future `Display` impl.
+    pub fn copy_string(&self, flags: Flags) -> Result<String> {
+        let mut text = MaybeUninit::uninit();
+
+        unsafe {
+            cvt(SecRequirementCopyString(
+                self.as_concrete_TypeRef(),
+                flags.bits(),
+                text.as_mut_ptr(),
+            ))?;
+
+            Ok(CFString::wrap_under_create_rule(text.assume_init()).to_string())
+        }
+    }
+
+    /// Serializes the requirement to its canonical binary representation,
+    /// the form Apple's own tooling stores and compares requirements in.
+    pub fn to_data(&self) -> Result<CFData> {
+        let mut data = MaybeUninit::uninit();
+
+        unsafe {
+            cvt(SecRequirementCopyData(
+                self.as_concrete_TypeRef(),
+                0,
+                data.as_mut_ptr(),
+            ))?;
+
+            Ok(CFData::wrap_under_create_rule(data.assume_init()))
+        }
+    }
+
+    /// Reconstructs a requirement previously serialized with
+    /// [`to_data`](Self::to_data).
+    pub fn from_data(data: &CFData) -> Result<Self> {
+        let mut requirement = MaybeUninit::uninit();
+
+        unsafe {
+            cvt(SecRequirementCreateWithData(
+                data.as_concrete_TypeRef(),
+                0,
+                requirement.as_mut_ptr(),
+            ))?;
+
+            Ok(Self::wrap_under_create_rule(requirement.assume_init()))
+        }
+    }
+}
+
 declare_TCFType! {
     /// A code object representing signed code running on the system.
     SecCode, SecCodeRef
@@ -241,6 +496,62 @@ impl SecCode {
             Ok(CFURL::wrap_under_create_rule(url.assume_init()))
         }
     }
+
+    /// Retrieves signing information about the code.
+    ///
+    /// This is the main way to inspect who signed a binary, which
+    /// certificates back it, and what entitlements it carries.
+    pub fn signing_information(&self, flags: InformationFlags) -> Result<SigningInformation> {
+        let mut info = MaybeUninit::uninit();
+
+        // The docs say we can pass a SecCodeRef instead of a SecStaticCodeRef.
+        unsafe {
+            cvt(SecCodeCopySigningInformation(
+                self.as_CFTypeRef() as _,
+                flags.bits(),
+                info.as_mut_ptr(),
+            ))?;
+
+            Ok(SigningInformation {
+                inner: CFDictionary::wrap_under_create_rule(info.assume_init()),
+            })
+        }
+    }
+
+    /// Extracts the designated requirement of the code: the requirement that
+    /// the signer embedded (or, absent that, the implicit requirement the
+    /// system would generate) to answer "is this the same program, signed by
+    /// the same party?"
+    pub fn designated_requirement(&self, flags: Flags) -> Result<SecRequirement> {
+        let mut requirement = MaybeUninit::uninit();
+
+        // The docs say we can pass a SecCodeRef instead of a SecStaticCodeRef.
+        unsafe {
+            cvt(SecCodeCopyDesignatedRequirement(
+                self.as_CFTypeRef() as _,
+                flags.bits(),
+                requirement.as_mut_ptr(),
+            ))?;
+
+            Ok(SecRequirement::wrap_under_create_rule(
+                requirement.assume_init(),
+            ))
+        }
+    }
+
+    /// Attaches a detached signature, previously produced by a
+    /// [`SecCodeSigner`] configured with
+    /// [`detached`](SecCodeSignerBuilder::detached), to this code object so
+    /// that it subsequently validates as if it had been signed on disk.
+    pub fn set_detached_signature(&self, signature: &CFData, flags: Flags) -> Result<()> {
+        unsafe {
+            cvt(SecCodeSetDetachedSignature(
+                self.as_concrete_TypeRef(),
+                signature.as_concrete_TypeRef(),
+                flags.bits(),
+            ))
+        }
+    }
 }
 
 declare_TCFType! {
@@ -249,6 +560,64 @@ declare_TCFType! {
 }
 impl_TCFType!(SecStaticCode, SecStaticCodeRef, SecStaticCodeGetTypeID);
 
+// `SecCodeCallback`, as declared in `SecStaticCode.h`, is invoked with the
+// code being validated, the name of the validation stage being reported, and
+// a dictionary of stage-specific details, returning non-`NULL` to cancel the
+// remainder of the validation. The C entry point gives us no context pointer
+// to carry a closure in, so `SecStaticCode::set_validation_callback` keys a
+// side table of boxed closures by the `SecStaticCodeRef` this callback is
+// invoked with instead.
+type BoxedValidationCallback = Box<dyn FnMut(CFURL, u32) -> bool + Send>;
+
+fn validation_callbacks() -> &'static Mutex<HashMap<usize, BoxedValidationCallback>> {
+    static CALLBACKS: OnceLock<Mutex<HashMap<usize, BoxedValidationCallback>>> = OnceLock::new();
+    CALLBACKS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Pulls the path and remaining-object count a validation progress
+/// notification carries, if the stage's info dictionary carries them.
+fn decode_validation_progress(info: CFDictionaryRef) -> (CFURL, u32) {
+    let missing = || CFURL::from_path("/", true).unwrap();
+
+    if info.is_null() {
+        return (missing(), 0);
+    }
+
+    let info = unsafe { CFDictionary::wrap_under_get_rule(info) };
+
+    let path = CFString::new("path");
+    let path = info
+        .find(path.as_CFTypeRef() as *const c_void)
+        .map(|value| unsafe { CFURL::wrap_under_get_rule(*value as CFURLRef) })
+        .unwrap_or_else(missing);
+
+    let remaining = CFString::new("remaining");
+    let remaining = info
+        .find(remaining.as_CFTypeRef() as *const c_void)
+        .and_then(|value| unsafe { CFNumber::wrap_under_get_rule(*value as CFNumberRef) }.to_i64())
+        .unwrap_or(0) as u32;
+
+    (path, remaining)
+}
+
+extern "C" fn validation_callback_trampoline(
+    code: SecStaticCodeRef,
+    _stage: CFStringRef,
+    info: CFDictionaryRef,
+) -> CFTypeRef {
+    let mut callbacks = validation_callbacks().lock().unwrap();
+    let Some(callback) = callbacks.get_mut(&(code as usize)) else {
+        return std::ptr::null();
+    };
+
+    let (path, remaining) = decode_validation_progress(info);
+    if callback(path, remaining) {
+        std::ptr::null()
+    } else {
+        unsafe { kCFBooleanFalse as CFTypeRef }
+    }
+}
+
 impl SecStaticCode {
     /// Creates a static code object representing the code at a specified file
     /// system path.
@@ -293,6 +662,257 @@ impl SecStaticCode {
             ))
         }
     }
+
+    /// Retrieves signing information about the code.
+    ///
+    /// This is the main way to inspect who signed a binary, which
+    /// certificates back it, and what entitlements it carries.
+    pub fn signing_information(&self, flags: InformationFlags) -> Result<SigningInformation> {
+        let mut info = MaybeUninit::uninit();
+
+        unsafe {
+            cvt(SecCodeCopySigningInformation(
+                self.as_concrete_TypeRef(),
+                flags.bits(),
+                info.as_mut_ptr(),
+            ))?;
+
+            Ok(SigningInformation {
+                inner: CFDictionary::wrap_under_create_rule(info.assume_init()),
+            })
+        }
+    }
+
+    /// Extracts the designated requirement of the code: the requirement that
+    /// the signer embedded (or, absent that, the implicit requirement the
+    /// system would generate) to answer "is this the same program, signed by
+    /// the same party?"
+    pub fn designated_requirement(&self, flags: Flags) -> Result<SecRequirement> {
+        let mut requirement = MaybeUninit::uninit();
+
+        unsafe {
+            cvt(SecCodeCopyDesignatedRequirement(
+                self.as_concrete_TypeRef(),
+                flags.bits(),
+                requirement.as_mut_ptr(),
+            ))?;
+
+            Ok(SecRequirement::wrap_under_create_rule(
+                requirement.assume_init(),
+            ))
+        }
+    }
+
+    /// Performs dynamic validation of signed code, like
+    /// [`check_validity`](Self::check_validity), but surfaces the rich
+    /// diagnostics Gatekeeper produces on failure instead of a bare status
+    /// code.
+    ///
+    /// The returned [`CFError`]'s `userInfo` dictionary carries keys such as
+    /// `kSecCSErrorArchitecture`, `kSecCSErrorPath`, and
+    /// `kSecCSErrorResourceSeal` identifying exactly which nested bundle,
+    /// resource, or requirement clause failed to validate. `requirement` may
+    /// be omitted to only check the code's own internal consistency.
+    pub fn check_validity_with_errors(
+        &self,
+        flags: Flags,
+        requirement: Option<&SecRequirement>,
+    ) -> std::result::Result<(), CFError> {
+        let requirement = requirement
+            .map(|requirement| requirement.as_concrete_TypeRef())
+            .unwrap_or(std::ptr::null_mut());
+        let mut error = MaybeUninit::uninit();
+
+        let status = unsafe {
+            SecStaticCodeCheckValidityWithErrors(
+                self.as_concrete_TypeRef(),
+                flags.bits(),
+                requirement,
+                error.as_mut_ptr(),
+            )
+        };
+
+        if status == 0 {
+            Ok(())
+        } else {
+            Err(unsafe { CFError::wrap_under_create_rule(error.assume_init()) })
+        }
+    }
+
+    /// Installs `callback` to be invoked as validation proceeds, so that
+    /// validating a large app bundle can report status and be aborted
+    /// early. Takes effect on subsequent calls to
+    /// [`check_validity`](Self::check_validity) or
+    /// [`check_validity_with_errors`](Self::check_validity_with_errors) made
+    /// with [`Flags::REPORT_PROGRESS`], and remains installed until
+    /// replaced or cleared with
+    /// [`clear_validation_callback`](Self::clear_validation_callback).
+    ///
+    /// `callback` is called with the path of the nested code object just
+    /// validated and the number of objects remaining to check; returning
+    /// `false` cancels the rest of the traversal.
+    pub fn set_validation_callback<F>(&self, flags: Flags, callback: F) -> Result<()>
+    where
+        F: FnMut(CFURL, u32) -> bool + Send + 'static,
+    {
+        validation_callbacks()
+            .lock()
+            .unwrap()
+            .insert(self.as_concrete_TypeRef() as usize, Box::new(callback));
+
+        let mut previous = MaybeUninit::uninit();
+
+        unsafe {
+            cvt(SecStaticCodeSetCallback(
+                self.as_concrete_TypeRef(),
+                (flags | Flags::REPORT_PROGRESS).bits(),
+                validation_callback_trampoline,
+                previous.as_mut_ptr(),
+            ))
+        }
+    }
+
+    /// Removes the callback installed with
+    /// [`set_validation_callback`](Self::set_validation_callback), if any.
+    ///
+    /// [`validation_callback_trampoline`] stays registered with the
+    /// framework (there being no `self`-specific way to uninstall it), but
+    /// it tolerates a missing entry by reporting "continue", so dropping our
+    /// side-table entry is enough to make validation stop calling back into
+    /// Rust.
+    pub fn clear_validation_callback(&self) {
+        validation_callbacks()
+            .lock()
+            .unwrap()
+            .remove(&(self.as_concrete_TypeRef() as usize));
+    }
+}
+
+/// The parameters used to create a [`SecCodeSigner`].
+///
+/// Always construct this through [`new`](Self::new), never
+/// `SecCodeSignerBuilder { .. }` or a derived `Default`: `new` eagerly sets
+/// `kSecCodeSignerIdentity` to `kCFNull` so the resulting signer is ad-hoc
+/// until [`identity`](Self::identity) says otherwise, and a raw empty
+/// dictionary would be missing that mandatory key. A common use for an
+/// ad-hoc signer with [`detached`](Self::detached) set is to sign an
+/// otherwise unsigned binary purely to derive its designated requirement,
+/// without ever writing a signature to disk.
+pub struct SecCodeSignerBuilder {
+    params: CFMutableDictionary,
+}
+
+impl SecCodeSignerBuilder {
+    /// Creates an ad-hoc signing parameter set, as if `identity(None)` had
+    /// been called.
+    #[allow(clippy::new_without_default)] // deliberately not `Default`; see the struct docs
+    pub fn new() -> Self {
+        Self {
+            params: CFMutableDictionary::new(),
+        }
+        .identity(None)
+    }
+
+    /// The identity to sign with. `None` produces an ad-hoc signature.
+    ///
+    /// `SecCodeSignerCreate` treats this key as mandatory: an ad-hoc signer
+    /// requires it to be present and set to `kCFNull`, not merely absent, so
+    /// `None` is mapped to `CFNull` rather than leaving the key unset.
+    pub fn identity(mut self, identity: Option<&SecIdentity>) -> Self {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecCodeSignerIdentity) };
+        match identity {
+            Some(identity) => self.params.add(&key.as_CFTypeRef(), &identity.as_CFTypeRef()),
+            None => self.params.add(&key.as_CFTypeRef(), &CFNull::get().as_CFTypeRef()),
+        }
+        self
+    }
+
+    /// Captures the signature in `data` instead of writing it into the
+    /// target code on disk, producing a detached signature.
+    pub fn detached(mut self, data: &CFMutableData) -> Self {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecCodeSignerDetached) };
+        self.params.add(&key.as_CFTypeRef(), &data.as_CFTypeRef());
+        self
+    }
+
+    /// Overrides the identifier that would otherwise be inferred from the
+    /// code being signed.
+    pub fn identifier(mut self, identifier: &str) -> Self {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecCodeSignerIdentifier) };
+        let value = CFString::new(identifier);
+        self.params.add(&key.as_CFTypeRef(), &value.as_CFTypeRef());
+        self
+    }
+
+    /// The internal requirements, in requirement-language source form, to
+    /// embed in the signature.
+    pub fn requirements(mut self, requirements: &str) -> Self {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecCodeSignerRequirements) };
+        let value = CFString::new(requirements);
+        self.params.add(&key.as_CFTypeRef(), &value.as_CFTypeRef());
+        self
+    }
+
+    /// The entitlements dictionary to embed in the signature.
+    pub fn entitlements(mut self, entitlements: &CFDictionary) -> Self {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecCodeSignerEntitlements) };
+        self.params.add(&key.as_CFTypeRef(), &entitlements.as_CFTypeRef());
+        self
+    }
+
+    /// The `SecCodeSignatureFlags` to record in the signature.
+    pub fn flags(mut self, flags: u32) -> Self {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecCodeSignerFlags) };
+        let value = CFNumber::from(i64::from(flags));
+        self.params.add(&key.as_CFTypeRef(), &value.as_CFTypeRef());
+        self
+    }
+
+    /// The signing time to have a trusted timestamping authority attest to.
+    pub fn timestamp(mut self, timestamp: &CFDate) -> Self {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecCodeSignerSigningTime) };
+        self.params.add(&key.as_CFTypeRef(), &timestamp.as_CFTypeRef());
+        self
+    }
+
+    /// Creates the signer described by this builder.
+    pub fn build(self, flags: Flags) -> Result<SecCodeSigner> {
+        let mut signer = MaybeUninit::uninit();
+
+        unsafe {
+            cvt(SecCodeSignerCreate(
+                self.params.as_concrete_TypeRef(),
+                flags.bits(),
+                signer.as_mut_ptr(),
+            ))?;
+
+            Ok(SecCodeSigner::wrap_under_create_rule(signer.assume_init()))
+        }
+    }
+}
+
+declare_TCFType! {
+    /// An object that applies a digital signature to code on disk, or
+    /// produces a detached signature for it, as configured by a
+    /// [`SecCodeSignerBuilder`].
+    SecCodeSigner, SecCodeSignerRef
+}
+impl_TCFType!(SecCodeSigner, SecCodeSignerRef, SecCodeSignerGetTypeID);
+
+impl SecCodeSigner {
+    /// Applies this signer's signature to `code`, either writing it into the
+    /// code on disk or, if the builder specified
+    /// [`detached`](SecCodeSignerBuilder::detached), capturing it in memory
+    /// instead.
+    pub fn add_signature(&self, code: &SecStaticCode, flags: Flags) -> Result<()> {
+        unsafe {
+            cvt(SecCodeSignerAddSignature(
+                self.as_concrete_TypeRef(),
+                code.as_concrete_TypeRef(),
+                flags.bits(),
+            ))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -334,4 +954,43 @@ mod test {
             -67062
         );
     }
+
+    #[test]
+    fn bash_signing_information_is_populated() {
+        let path = CFURL::from_path("/bin/bash", false).unwrap();
+        let code = SecStaticCode::from_path(&path, Flags::NONE).unwrap();
+        let info = code
+            .signing_information(InformationFlags::SIGNING_INFORMATION)
+            .unwrap();
+
+        assert!(info.identifier().is_some());
+        assert!(!info.certificates().unwrap().is_empty());
+    }
+
+    #[test]
+    fn bash_satisfies_its_own_designated_requirement() {
+        let path = CFURL::from_path("/bin/bash", false).unwrap();
+        let code = SecStaticCode::from_path(&path, Flags::NONE).unwrap();
+        let requirement = code.designated_requirement(Flags::NONE).unwrap();
+        code.check_validity(Flags::NONE, &requirement).unwrap();
+    }
+
+    #[test]
+    fn requirement_round_trips_through_data() {
+        let requirement: SecRequirement = "anchor apple".parse().unwrap();
+        let data = requirement.to_data().unwrap();
+        let requirement = SecRequirement::from_data(&data).unwrap();
+        assert_eq!(requirement.copy_string(Flags::NONE).unwrap(), "anchor apple");
+    }
+
+    #[test]
+    fn validation_callback_can_be_installed_and_cleared() {
+        let path = CFURL::from_path("/bin/bash", false).unwrap();
+        let code = SecStaticCode::from_path(&path, Flags::NONE).unwrap();
+
+        code.set_validation_callback(Flags::NONE, |_path, _remaining| true)
+            .unwrap();
+        code.check_validity_with_errors(Flags::NONE, None).unwrap();
+        code.clear_validation_callback();
+    }
 }